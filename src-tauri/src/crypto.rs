@@ -0,0 +1,113 @@
+//! At-rest encryption for the persisted session store.
+//!
+//! A master password (when the user opts into one) derives a key with
+//! Argon2id; the serialized session data is then sealed with
+//! ChaCha20-Poly1305. Passwords and derived keys are zeroized as soon as
+//! they've been used — including, via [`SecretString`], the plaintext
+//! password `String`s that arrive over IPC.
+
+use std::ops::Deref;
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, OsRng as AeadOsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, KeyInit, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EncryptedBlob {
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// A password `String` that zeroizes its contents on drop. Tauri commands
+/// take passwords as this type instead of a plain `String` so the plaintext
+/// doesn't linger on the heap after the command handler returns.
+#[derive(Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+fn derive_key(password: &[u8], salt: &[u8; 16]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password, salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+pub fn encrypt(password: &str, plaintext: &[u8]) -> Result<EncryptedBlob, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut password_bytes = password.as_bytes().to_vec();
+    let mut key = derive_key(&password_bytes, &salt)?;
+    password_bytes.zeroize();
+
+    let cipher = ChaCha20Poly1305::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut AeadOsRng);
+    let result = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| format!("Encryption failed: {e}"));
+    key.zeroize();
+    let ciphertext = result?;
+
+    Ok(EncryptedBlob {
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+pub fn decrypt(password: &str, blob: &EncryptedBlob) -> Result<Vec<u8>, String> {
+    let salt = BASE64
+        .decode(&blob.salt)
+        .map_err(|_| "Corrupt session data".to_string())?;
+    let salt: [u8; 16] = salt
+        .try_into()
+        .map_err(|_| "Corrupt session data".to_string())?;
+    let nonce_bytes = BASE64
+        .decode(&blob.nonce)
+        .map_err(|_| "Corrupt session data".to_string())?;
+    let ciphertext = BASE64
+        .decode(&blob.ciphertext)
+        .map_err(|_| "Corrupt session data".to_string())?;
+
+    if nonce_bytes.len() != 12 {
+        return Err("Corrupt session data".to_string());
+    }
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let mut password_bytes = password.as_bytes().to_vec();
+    let mut key = derive_key(&password_bytes, &salt)?;
+    password_bytes.zeroize();
+
+    let cipher = ChaCha20Poly1305::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let result = cipher.decrypt(nonce, ciphertext.as_ref());
+    key.zeroize();
+
+    result.map_err(|_| "wrong password".to_string())
+}
@@ -0,0 +1,211 @@
+//! Background grade polling.
+//!
+//! Periodically re-fetches `/feign/student/grades/all` for the active
+//! account, diffs it against the last known snapshot, and surfaces
+//! anything new or changed as a Tauri event plus a native notification.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::{api_get, AppState, TRAY_ID};
+
+const MIN_POLL_INTERVAL_SECS: u64 = 60;
+
+#[derive(Clone, Serialize)]
+struct GradeChange {
+    #[serde(rename = "courseSyllabusId")]
+    course_syllabus_id: String,
+    #[serde(rename = "examPeriodId")]
+    exam_period_id: String,
+    grade: String,
+    #[serde(rename = "previousGrade")]
+    previous_grade: Option<String>,
+}
+
+/// Labels come from user-chosen account names, so sanitize before using one
+/// as part of a filename.
+fn sanitize_label(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn snapshot_path(app: &AppHandle, label: &str) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(format!("grades_snapshot_{}.json", sanitize_label(label))))
+}
+
+fn snapshot_exists(app: &AppHandle, label: &str) -> bool {
+    match snapshot_path(app, label) {
+        Ok(path) => path.exists(),
+        Err(_) => false,
+    }
+}
+
+fn load_snapshot(app: &AppHandle, label: &str) -> HashMap<String, String> {
+    let path = match snapshot_path(app, label) {
+        Ok(p) => p,
+        Err(_) => return HashMap::new(),
+    };
+    let data = match std::fs::read_to_string(&path) {
+        Ok(d) => d,
+        Err(_) => return HashMap::new(),
+    };
+    serde_json::from_str(&data).unwrap_or_default()
+}
+
+fn save_snapshot(app: &AppHandle, label: &str, snapshot: &HashMap<String, String>) -> Result<(), String> {
+    let path = snapshot_path(app, label)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, serde_json::to_string(snapshot).unwrap()).map_err(|e| e.to_string())
+}
+
+fn grade_value_to_string(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Flattens the `grades/all` response into `"courseSyllabusId:examPeriodId" -> grade`.
+fn extract_current_grades(grades: &Value) -> HashMap<String, String> {
+    let mut current = HashMap::new();
+    let Some(entries) = grades.as_array() else {
+        return current;
+    };
+    for entry in entries {
+        let course_syllabus_id = entry.get("courseSyllabusId").and_then(grade_value_to_string);
+        let exam_period_id = entry.get("examPeriodId").and_then(grade_value_to_string);
+        let grade = entry.get("grade").and_then(grade_value_to_string);
+        if let (Some(course_syllabus_id), Some(exam_period_id), Some(grade)) =
+            (course_syllabus_id, exam_period_id, grade)
+        {
+            current.insert(format!("{course_syllabus_id}:{exam_period_id}"), grade);
+        }
+    }
+    current
+}
+
+/// The label of the currently active account, used to key the per-account
+/// grade snapshot so switching accounts doesn't diff one account's grades
+/// against another's leftover snapshot.
+fn active_account_label(app: &AppHandle) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let active = state.active_label.lock().map_err(|e| e.to_string())?;
+    active.clone().ok_or_else(|| "Not logged in".to_string())
+}
+
+/// Fetches the active account's grades, diffs them against that account's
+/// saved snapshot, and emits an event + notification per new or changed grade.
+async fn poll_once(app: &AppHandle) -> Result<(), String> {
+    let label = active_account_label(app)?;
+    let grades = api_get(app, "/feign/student/grades/all").await?;
+    let current = extract_current_grades(&grades);
+
+    if !snapshot_exists(app, &label) {
+        // First poll ever for this account label (snapshots are kept on disk
+        // across restarts and re-logins): there's no prior snapshot to diff
+        // against, so every grade the student
+        // already had would otherwise look "new". Seed the baseline silently
+        // instead of notification-spamming grades they've known about for
+        // months.
+        return save_snapshot(app, &label, &current);
+    }
+
+    let previous = load_snapshot(app, &label);
+
+    let changes: Vec<GradeChange> = current
+        .iter()
+        .filter_map(|(key, grade)| {
+            let previous_grade = previous.get(key).cloned();
+            if previous_grade.as_deref() == Some(grade.as_str()) {
+                return None;
+            }
+            let (course_syllabus_id, exam_period_id) = key.split_once(':')?;
+            Some(GradeChange {
+                course_syllabus_id: course_syllabus_id.to_string(),
+                exam_period_id: exam_period_id.to_string(),
+                grade: grade.clone(),
+                previous_grade,
+            })
+        })
+        .collect();
+
+    if !changes.is_empty() {
+        for change in &changes {
+            let _ = app.emit("grade-updated", change);
+            let _ = app
+                .notification()
+                .builder()
+                .title("New grade")
+                .body(format!("Course {}: {}", change.course_syllabus_id, change.grade))
+                .show();
+        }
+
+        let state = app.state::<AppState>();
+        if let Ok(mut unseen) = state.poll_unseen.lock() {
+            *unseen += changes.len() as u32;
+            update_tray_badge(app, *unseen);
+        }
+    }
+
+    save_snapshot(app, &label, &current)
+}
+
+/// Updates the tray tooltip (and, on macOS, the menu-bar title) to reflect
+/// how many unseen grade changes have arrived since the window was last shown.
+pub(crate) fn update_tray_badge(app: &AppHandle, unseen: u32) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let tooltip = if unseen > 0 {
+        format!("UoM Grades ({unseen} new)")
+    } else {
+        "UoM Grades".to_string()
+    };
+    let _ = tray.set_tooltip(Some(tooltip.as_str()));
+    let badge = if unseen > 0 { unseen.to_string() } else { String::new() };
+    let _ = tray.set_title(Some(badge.as_str()));
+}
+
+/// Clears the unseen-count badge, called whenever the main window is shown.
+pub(crate) fn reset_unseen(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if let Ok(mut unseen) = state.poll_unseen.lock() {
+        *unseen = 0;
+    }
+    update_tray_badge(app, 0);
+}
+
+fn current_poll_interval(app: &AppHandle) -> Duration {
+    let state = app.state::<AppState>();
+    let secs = state
+        .settings
+        .lock()
+        .map(|s| s.poll_interval_secs)
+        .unwrap_or(crate::DEFAULT_POLL_INTERVAL_SECS);
+    Duration::from_secs(secs.max(MIN_POLL_INTERVAL_SECS))
+}
+
+/// Spawns the background polling loop. The interval is re-read from
+/// `AppSettings` on every cycle, so `set_poll_interval` takes effect
+/// starting with the next tick rather than requiring a restart.
+pub(crate) fn spawn_polling_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(current_poll_interval(&app)).await;
+            let _ = poll_once(&app).await;
+        }
+    });
+}
@@ -1,3 +1,7 @@
+mod crypto;
+mod grades;
+
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -6,16 +10,20 @@ use reqwest::Client;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tauri::menu::{Menu, MenuItem};
+use tauri::menu::{IsMenuItem, Menu, MenuItem, Submenu};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::Manager;
 use tauri::WindowEvent;
 use url::Url;
 
+use crypto::{EncryptedBlob, SecretString};
+
 const SSO_URL: &str = "https://sso.uom.gr/login";
 const PORTAL_URL: &str = "https://sis-portal.uom.gr";
 const SERVICE_URL: &str = "https://sis-portal.uom.gr/login/cas";
 const UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+const TRAY_ID: &str = "main-tray";
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 1800;
 
 // ── Session types ───────────────────────────────────────────────────
 
@@ -25,23 +33,75 @@ struct SessionData {
     profile_id: String,
 }
 
-#[derive(Clone, Default, Serialize, Deserialize)]
+struct Account {
+    username: String,
+    session: SessionData,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub keep_in_tray: bool,
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_poll_interval_secs() -> u64 {
+    DEFAULT_POLL_INTERVAL_SECS
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            keep_in_tray: false,
+            poll_interval_secs: default_poll_interval_secs(),
+        }
+    }
 }
 
 pub struct AppState {
-    session: Mutex<Option<SessionData>>,
+    accounts: Mutex<HashMap<String, Account>>,
+    active_label: Mutex<Option<String>>,
     settings: Mutex<AppSettings>,
+    poll_unseen: Mutex<u32>,
 }
 
 #[derive(Serialize, Deserialize)]
-struct SavedSession {
+struct SavedAccount {
+    label: String,
+    username: String,
     portal_cookies: String,
     csrf: String,
     profile_id: String,
 }
 
+#[derive(Default, Serialize, Deserialize)]
+struct SavedAccounts {
+    accounts: Vec<SavedAccount>,
+    active_label: Option<String>,
+}
+
+/// On-disk shape of `accounts.json`. Plaintext by default; `Encrypted` once
+/// the user opts into a master password (see [`crypto`]).
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+enum AccountsFile {
+    Plain(SavedAccounts),
+    Encrypted(EncryptedBlob),
+}
+
+impl Default for AccountsFile {
+    fn default() -> Self {
+        AccountsFile::Plain(SavedAccounts::default())
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct AccountInfo {
+    label: String,
+    username: String,
+    active: bool,
+}
+
 // ── Sync HTML helpers (scraper types are !Send) ─────────────────────
 
 fn extract_cas_tokens(html: &str) -> Result<(String, Option<String>), String> {
@@ -96,9 +156,9 @@ fn find_profile_id(value: &Value) -> Option<String> {
 
 // ── Session persistence helpers ─────────────────────────────────────
 
-fn session_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+fn accounts_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    Ok(dir.join("session.json"))
+    Ok(dir.join("accounts.json"))
 }
 
 fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
@@ -126,11 +186,75 @@ fn save_settings(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), S
     std::fs::write(&path, serde_json::to_string(settings).unwrap()).map_err(|e| e.to_string())
 }
 
-fn save_session_to_disk(
+fn read_raw_accounts_file(app: &tauri::AppHandle) -> AccountsFile {
+    let path = match accounts_path(app) {
+        Ok(p) => p,
+        Err(_) => return AccountsFile::default(),
+    };
+    let data = match std::fs::read_to_string(&path) {
+        Ok(d) => d,
+        Err(_) => return AccountsFile::default(),
+    };
+    serde_json::from_str(&data).unwrap_or_default()
+}
+
+/// Loads and decrypts (if needed) the saved accounts. `master_password` must
+/// be supplied whenever the file on disk is in encrypted mode.
+fn load_accounts_from_disk(
+    app: &tauri::AppHandle,
+    master_password: Option<&str>,
+) -> Result<SavedAccounts, String> {
+    match read_raw_accounts_file(app) {
+        AccountsFile::Plain(saved) => Ok(saved),
+        AccountsFile::Encrypted(blob) => {
+            let password = master_password.ok_or("Master password required")?;
+            let plaintext = crypto::decrypt(password, &blob)?;
+            serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Writes the accounts back to disk, preserving whichever mode (plain or
+/// encrypted) the caller is operating in. Passing a password always
+/// (re-)encrypts with a fresh salt/nonce; passing `None` writes plaintext,
+/// unless the file is already encrypted, in which case the caller must
+/// supply the password rather than silently removing the protection.
+fn write_accounts_to_disk(
     app: &tauri::AppHandle,
+    saved: &SavedAccounts,
+    master_password: Option<&str>,
+) -> Result<(), String> {
+    let file = match master_password {
+        Some(password) => {
+            let plaintext = serde_json::to_vec(saved).map_err(|e| e.to_string())?;
+            AccountsFile::Encrypted(crypto::encrypt(password, &plaintext)?)
+        }
+        None => {
+            if matches!(read_raw_accounts_file(app), AccountsFile::Encrypted(_)) {
+                return Err("Master password required".to_string());
+            }
+            AccountsFile::Plain(SavedAccounts {
+                accounts: saved.accounts.clone(),
+                active_label: saved.active_label.clone(),
+            })
+        }
+    };
+
+    let path = accounts_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, serde_json::to_string(&file).unwrap()).map_err(|e| e.to_string())
+}
+
+fn save_account_to_disk(
+    app: &tauri::AppHandle,
+    label: &str,
+    username: &str,
     jar: &Jar,
     csrf: &str,
     profile_id: &str,
+    master_password: Option<&str>,
 ) -> Result<(), String> {
     let portal_url: Url = PORTAL_URL.parse().unwrap();
     let cookies = jar
@@ -138,23 +262,40 @@ fn save_session_to_disk(
         .and_then(|h| h.to_str().ok().map(|s| s.to_string()))
         .unwrap_or_default();
 
-    let saved = SavedSession {
+    let mut saved = load_accounts_from_disk(app, master_password)?;
+    saved.accounts.retain(|a| a.label != label);
+    saved.accounts.push(SavedAccount {
+        label: label.to_string(),
+        username: username.to_string(),
         portal_cookies: cookies,
         csrf: csrf.to_string(),
         profile_id: profile_id.to_string(),
-    };
+    });
+    saved.active_label = Some(label.to_string());
+    write_accounts_to_disk(app, &saved, master_password)
+}
 
-    let path = session_path(app)?;
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
-    std::fs::write(&path, serde_json::to_string(&saved).unwrap()).map_err(|e| e.to_string())
+fn save_active_label(
+    app: &tauri::AppHandle,
+    label: Option<&str>,
+    master_password: Option<&str>,
+) -> Result<(), String> {
+    let mut saved = load_accounts_from_disk(app, master_password)?;
+    saved.active_label = label.map(|s| s.to_string());
+    write_accounts_to_disk(app, &saved, master_password)
 }
 
-fn delete_session_from_disk(app: &tauri::AppHandle) {
-    if let Ok(path) = session_path(app) {
-        let _ = std::fs::remove_file(path);
+fn delete_account_from_disk(
+    app: &tauri::AppHandle,
+    label: &str,
+    master_password: Option<&str>,
+) -> Result<(), String> {
+    let mut saved = load_accounts_from_disk(app, master_password)?;
+    saved.accounts.retain(|a| a.label != label);
+    if saved.active_label.as_deref() == Some(label) {
+        saved.active_label = None;
     }
+    write_accounts_to_disk(app, &saved, master_password)
 }
 
 fn build_client_from_cookies(cookies: &str) -> Result<Client, String> {
@@ -175,94 +316,240 @@ fn build_client_from_cookies(cookies: &str) -> Result<Client, String> {
         .map_err(|e| format!("HTTP client error: {e}"))
 }
 
+// ── IPC authorization ────────────────────────────────────────────────
+
+/// Rejects commands invoked from anywhere other than the app's own `main`
+/// webview loaded from the bundled frontend — e.g. a page that somehow
+/// navigated the webview to a remote site can't use it to exfiltrate the
+/// portal session via IPC.
+fn assert_trusted_caller(window: &tauri::WebviewWindow) -> Result<(), String> {
+    if window.label() != "main" {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    let url = window.url().map_err(|e| e.to_string())?;
+    let trusted_origin = match url.scheme() {
+        "tauri" => true,
+        "https" => url.host_str() == Some("tauri.localhost"),
+        _ => false,
+    };
+    if !trusted_origin {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(())
+}
+
 // ── Sync state helpers ──────────────────────────────────────────────
 
 fn extract_session(app: &tauri::AppHandle) -> Result<(Client, String, String), String> {
     let state = app.state::<AppState>();
-    let guard = state.session.lock().map_err(|e| e.to_string())?;
-    let s = guard.as_ref().ok_or("Not logged in")?;
-    Ok((s.client.clone(), s.csrf.clone(), s.profile_id.clone()))
+    let active = state.active_label.lock().map_err(|e| e.to_string())?;
+    let label = active.as_ref().ok_or("Not logged in")?;
+    let accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+    let acc = accounts.get(label).ok_or("Not logged in")?;
+    Ok((
+        acc.session.client.clone(),
+        acc.session.csrf.clone(),
+        acc.session.profile_id.clone(),
+    ))
 }
 
-fn store_session(app: &tauri::AppHandle, data: SessionData) -> Result<(), String> {
+fn store_account(
+    app: &tauri::AppHandle,
+    label: &str,
+    username: &str,
+    data: SessionData,
+) -> Result<(), String> {
     let state = app.state::<AppState>();
-    let mut guard = state.session.lock().map_err(|e| e.to_string())?;
-    *guard = Some(data);
+    let mut accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+    accounts.insert(
+        label.to_string(),
+        Account {
+            username: username.to_string(),
+            session: data,
+        },
+    );
+    drop(accounts);
+    let mut active = state.active_label.lock().map_err(|e| e.to_string())?;
+    *active = Some(label.to_string());
     Ok(())
 }
 
+// ── Typed API errors ─────────────────────────────────────────────────
+
+/// Error surfaced by the authenticated API helpers. Unlike the plain
+/// `String` errors used elsewhere, this lets the frontend distinguish "the
+/// portal session is dead, show the login screen" from an ordinary message.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message")]
+enum ApiError {
+    SessionExpired,
+    Network(String),
+    Other(String),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::SessionExpired => write!(f, "Session expired"),
+            ApiError::Network(msg) => write!(f, "Network error: {msg}"),
+            ApiError::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(msg: String) -> Self {
+        ApiError::Other(msg)
+    }
+}
+
+impl From<ApiError> for String {
+    fn from(e: ApiError) -> String {
+        e.to_string()
+    }
+}
+
 // ── Async helper: authenticated API GET ─────────────────────────────
 
-async fn api_get(
+const API_GET_MAX_ATTEMPTS: u32 = 3;
+
+/// Sends the authenticated GET, retrying transient network failures with
+/// exponential backoff (200ms, 400ms, ...).
+async fn send_with_retries(
+    client: &Client,
+    url: &str,
+    csrf: &str,
+    profile_id: &str,
+) -> Result<reqwest::Response, ApiError> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match client
+            .get(url)
+            .header("X-CSRF-TOKEN", csrf)
+            .header("X-Profile", profile_id)
+            .header("X-Requested-With", "XMLHttpRequest")
+            .header("Accept", "application/json")
+            .send()
+            .await
+        {
+            Ok(resp) => return Ok(resp),
+            Err(_) if attempt < API_GET_MAX_ATTEMPTS => {
+                let backoff_ms = 200u64 * 2u64.pow(attempt - 1);
+                tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            }
+            Err(e) => return Err(ApiError::Network(e.to_string())),
+        }
+    }
+}
+
+/// A portal session that expired silently redirects to CAS, or serves the
+/// HTML login page instead of JSON, rather than returning an HTTP error.
+fn looks_like_expired_session(resp: &reqwest::Response) -> bool {
+    if resp.url().host_str() == Some("sso.uom.gr") {
+        return true;
+    }
+    resp.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/html"))
+}
+
+/// Stateless authenticated GET: no knowledge of `AppState`, used by flows
+/// (login, session restore) that don't yet have an in-memory session to
+/// refresh against.
+async fn api_get_raw(
     client: &Client,
     path: &str,
     csrf: &str,
     profile_id: &str,
-) -> Result<Value, String> {
-    let resp = client
-        .get(format!("{PORTAL_URL}{path}"))
-        .header("X-CSRF-TOKEN", csrf)
-        .header("X-Profile", profile_id)
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {e}"))?;
+) -> Result<Value, ApiError> {
+    let url = format!("{PORTAL_URL}{path}");
+    let resp = send_with_retries(client, &url, csrf, profile_id).await?;
+
+    if looks_like_expired_session(&resp) {
+        return Err(ApiError::SessionExpired);
+    }
 
     resp.json::<Value>()
         .await
-        .map_err(|e| format!("Invalid JSON: {e}"))
+        .map_err(|e| ApiError::Other(format!("Invalid JSON: {e}")))
 }
 
-// ── Command: try_restore_session ────────────────────────────────────
+/// Re-fetches the portal home page and extracts a fresh CSRF token.
+async fn refresh_csrf(client: &Client) -> Result<String, ApiError> {
+    let portal_html = client
+        .get(PORTAL_URL)
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| ApiError::Other(e.to_string()))?;
+    extract_csrf(&portal_html).map_err(ApiError::Other)
+}
 
-#[tauri::command]
-async fn try_restore_session(app: tauri::AppHandle) -> Result<Value, String> {
-    let path = session_path(&app)?;
-    let data = std::fs::read_to_string(&path).map_err(|_| "No saved session".to_string())?;
-    let saved: SavedSession =
-        serde_json::from_str(&data).map_err(|_| "Corrupt session file".to_string())?;
-
-    if saved.portal_cookies.is_empty() {
-        return Err("Empty session".to_string());
+/// Best-effort persistence of a refreshed CSRF token back to `accounts.json`.
+/// Only the in-memory copy is load-bearing for the current session, so a
+/// failure here (notably: the file is encrypted and we don't have the master
+/// password on hand) is not fatal — it just means the next restore will have
+/// to refresh again.
+fn persist_refreshed_csrf(app: &tauri::AppHandle, label: &str, csrf: &str) {
+    let Ok(mut saved) = load_accounts_from_disk(app, None) else {
+        return;
+    };
+    if let Some(acc) = saved.accounts.iter_mut().find(|a| a.label == label) {
+        acc.csrf = csrf.to_string();
+        let _ = write_accounts_to_disk(app, &saved, None);
     }
+}
 
-    let client = build_client_from_cookies(&saved.portal_cookies)?;
-
-    // Verify the session is still valid
-    let student_info = api_get(
-        &client,
-        "/feign/student/student_data",
-        &saved.csrf,
-        &saved.profile_id,
-    )
-    .await
-    .map_err(|_| {
-        delete_session_from_disk(&app);
-        "Session expired".to_string()
-    })?;
-
-    // Session works — store it in memory
-    store_session(
-        &app,
-        SessionData {
-            client,
-            csrf: saved.csrf,
-            profile_id: saved.profile_id,
-        },
-    )?;
+fn update_active_csrf(app: &tauri::AppHandle, csrf: &str) -> Result<(), ApiError> {
+    let state = app.state::<AppState>();
+    let label = {
+        let active = state
+            .active_label
+            .lock()
+            .map_err(|e| ApiError::Other(e.to_string()))?;
+        active.clone().ok_or(ApiError::SessionExpired)?
+    };
+    {
+        let mut accounts = state
+            .accounts
+            .lock()
+            .map_err(|e| ApiError::Other(e.to_string()))?;
+        let acc = accounts.get_mut(&label).ok_or(ApiError::SessionExpired)?;
+        acc.session.csrf = csrf.to_string();
+    }
+    persist_refreshed_csrf(app, &label, csrf);
+    Ok(())
+}
 
-    Ok(student_info)
+/// Authenticated GET for the active account. If the portal bounces the
+/// request to CAS (an expired CSRF token, not necessarily a dead cookie
+/// jar), transparently refreshes the CSRF token once and retries before
+/// giving up with [`ApiError::SessionExpired`].
+async fn api_get(app: &tauri::AppHandle, path: &str) -> Result<Value, ApiError> {
+    let (client, csrf, profile_id) = extract_session(app)?;
+
+    match api_get_raw(&client, path, &csrf, &profile_id).await {
+        Err(ApiError::SessionExpired) => {
+            let refreshed_csrf = refresh_csrf(&client).await?;
+            update_active_csrf(app, &refreshed_csrf)?;
+            api_get_raw(&client, path, &refreshed_csrf, &profile_id).await
+        }
+        other => other,
+    }
 }
 
-// ── Command: login ──────────────────────────────────────────────────
+// ── Async helper: CAS login flow ─────────────────────────────────────
 
-#[tauri::command]
-async fn login(
-    username: String,
-    password: String,
-    app: tauri::AppHandle,
-) -> Result<Value, String> {
+async fn perform_login(
+    username: &str,
+    password: &str,
+) -> Result<(Client, Arc<Jar>, String, String, Value), String> {
     let jar = Arc::new(Jar::default());
     let client = Client::builder()
         .cookie_provider(jar.clone())
@@ -293,8 +580,8 @@ async fn login(
 
     // 4. Submit credentials
     let mut form: Vec<(&str, String)> = vec![
-        ("username", username),
-        ("password", password),
+        ("username", username.to_string()),
+        ("password", password.to_string()),
         ("execution", execution),
         ("_eventId", "submit".to_string()),
     ];
@@ -345,31 +632,316 @@ async fn login(
 
     // 7. Fetch student info
     let student_info =
-        api_get(&client, "/feign/student/student_data", &csrf, &profile_id).await?;
+        api_get_raw(&client, "/feign/student/student_data", &csrf, &profile_id).await?;
+
+    Ok((client, jar, csrf, profile_id, student_info))
+}
+
+// ── Async helper: load a saved account into memory ───────────────────
 
-    // 8. Save session to disk (cookies + CSRF + profile)
-    let _ = save_session_to_disk(&app, &jar, &csrf, &profile_id);
+async fn restore_account_into_memory(
+    app: &tauri::AppHandle,
+    label: &str,
+    master_password: Option<&str>,
+) -> Result<Value, ApiError> {
+    let saved = load_accounts_from_disk(app, master_password).map_err(ApiError::Other)?;
+    let account = saved
+        .accounts
+        .iter()
+        .find(|a| a.label == label)
+        .ok_or_else(|| ApiError::Other("No such saved account".to_string()))?;
+
+    if account.portal_cookies.is_empty() {
+        return Err(ApiError::SessionExpired);
+    }
 
-    // 9. Store in memory
-    store_session(&app, SessionData { client, csrf, profile_id })?;
+    let client = build_client_from_cookies(&account.portal_cookies).map_err(ApiError::Other)?;
+
+    // The saved CSRF token may simply be stale (the portal rotates it
+    // periodically) rather than the cookie jar being dead, so refresh and
+    // retry once — same recovery path `api_get` takes — before concluding
+    // the whole session is gone. Network errors surfaced by either attempt
+    // propagate as-is so callers can tell "offline, try again" apart from an
+    // actually dead session.
+    let (csrf, student_info) = match api_get_raw(
+        &client,
+        "/feign/student/student_data",
+        &account.csrf,
+        &account.profile_id,
+    )
+    .await
+    {
+        Ok(info) => (account.csrf.clone(), info),
+        Err(ApiError::SessionExpired) => {
+            let refreshed_csrf = refresh_csrf(&client).await?;
+            let info = api_get_raw(
+                &client,
+                "/feign/student/student_data",
+                &refreshed_csrf,
+                &account.profile_id,
+            )
+            .await?;
+            (refreshed_csrf, info)
+        }
+        Err(e) => return Err(e),
+    };
+
+    store_account(
+        app,
+        label,
+        &account.username,
+        SessionData {
+            client,
+            csrf: csrf.clone(),
+            profile_id: account.profile_id.clone(),
+        },
+    )
+    .map_err(ApiError::Other)?;
+
+    if csrf != account.csrf {
+        persist_refreshed_csrf(app, label, &csrf);
+    }
 
     Ok(student_info)
 }
 
+// ── Command: try_restore_session ────────────────────────────────────
+
+#[tauri::command]
+async fn try_restore_session(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<Value, String> {
+    assert_trusted_caller(&window)?;
+
+    let label = load_accounts_from_disk(&app, master_password.as_deref())?
+        .active_label
+        .ok_or("No saved session")?;
+
+    let result = restore_account_into_memory(&app, &label, master_password.as_deref()).await;
+    // Only wipe the saved account when the portal actually rejected the
+    // session — never for a transient network error (checked on the typed
+    // `ApiError`, before it's stringified) or a wrong/missing master
+    // password, both of which are recoverable without a full re-login.
+    if let Err(ApiError::SessionExpired) = result {
+        let _ = delete_account_from_disk(&app, &label, master_password.as_deref());
+    }
+    let _ = rebuild_tray_menu(&app);
+    result.map_err(String::from)
+}
+
+// ── Command: login ──────────────────────────────────────────────────
+
+#[tauri::command]
+async fn login(
+    username: String,
+    password: SecretString,
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<Value, String> {
+    assert_trusted_caller(&window)?;
+
+    let (client, jar, csrf, profile_id, student_info) =
+        perform_login(&username, &password).await?;
+
+    // A bare login is stored as its own account, labeled by username.
+    let _ = save_account_to_disk(
+        &app,
+        &username,
+        &username,
+        &jar,
+        &csrf,
+        &profile_id,
+        master_password.as_deref(),
+    );
+    store_account(
+        &app,
+        &username,
+        &username,
+        SessionData {
+            client,
+            csrf,
+            profile_id,
+        },
+    )?;
+    let _ = rebuild_tray_menu(&app);
+
+    Ok(student_info)
+}
+
+// ── Command: list_accounts ──────────────────────────────────────────
+
+#[tauri::command]
+fn list_accounts(app: tauri::AppHandle) -> Result<Vec<AccountInfo>, String> {
+    let state = app.state::<AppState>();
+    let accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+    let active = state.active_label.lock().map_err(|e| e.to_string())?;
+    Ok(accounts
+        .iter()
+        .map(|(label, acc)| AccountInfo {
+            label: label.clone(),
+            username: acc.username.clone(),
+            active: active.as_deref() == Some(label.as_str()),
+        })
+        .collect())
+}
+
+// ── Command: add_account ────────────────────────────────────────────
+
+#[tauri::command]
+async fn add_account(
+    label: String,
+    username: String,
+    password: SecretString,
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<Value, String> {
+    assert_trusted_caller(&window)?;
+
+    let (client, jar, csrf, profile_id, student_info) =
+        perform_login(&username, &password).await?;
+
+    let _ = save_account_to_disk(
+        &app,
+        &label,
+        &username,
+        &jar,
+        &csrf,
+        &profile_id,
+        master_password.as_deref(),
+    );
+    store_account(
+        &app,
+        &label,
+        &username,
+        SessionData {
+            client,
+            csrf,
+            profile_id,
+        },
+    )?;
+    let _ = rebuild_tray_menu(&app);
+
+    Ok(student_info)
+}
+
+// ── Command: switch_account ─────────────────────────────────────────
+
+#[tauri::command]
+async fn switch_account(
+    label: String,
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<(), String> {
+    assert_trusted_caller(&window)?;
+
+    let already_loaded = {
+        let state = app.state::<AppState>();
+        let accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+        accounts.contains_key(&label)
+    };
+
+    if !already_loaded {
+        restore_account_into_memory(&app, &label, master_password.as_deref()).await?;
+    }
+
+    let state = app.state::<AppState>();
+    let mut active = state.active_label.lock().map_err(|e| e.to_string())?;
+    *active = Some(label.clone());
+    drop(active);
+
+    save_active_label(&app, Some(&label), master_password.as_deref())?;
+    let _ = rebuild_tray_menu(&app);
+    Ok(())
+}
+
+// ── Command: remove_account ─────────────────────────────────────────
+
+#[tauri::command]
+fn remove_account(
+    label: String,
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<(), String> {
+    assert_trusted_caller(&window)?;
+
+    let state = app.state::<AppState>();
+    {
+        let mut accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+        accounts.remove(&label);
+    }
+    {
+        let mut active = state.active_label.lock().map_err(|e| e.to_string())?;
+        if active.as_deref() == Some(label.as_str()) {
+            *active = None;
+        }
+    }
+    delete_account_from_disk(&app, &label, master_password.as_deref())?;
+    let _ = rebuild_tray_menu(&app);
+    Ok(())
+}
+
+// ── Command: set_master_password ────────────────────────────────────
+
+/// Enables (or rotates) master-password encryption of `accounts.json`.
+/// `current_master_password` must match whatever mode the file is
+/// currently in (`None` if it's still plaintext).
+#[tauri::command]
+fn set_master_password(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    current_master_password: Option<SecretString>,
+    new_master_password: SecretString,
+) -> Result<(), String> {
+    assert_trusted_caller(&window)?;
+
+    let saved = load_accounts_from_disk(&app, current_master_password.as_deref())?;
+    write_accounts_to_disk(&app, &saved, Some(new_master_password.as_str()))
+}
+
+// ── Command: clear_master_password ──────────────────────────────────
+
+/// Disables master-password encryption, reverting `accounts.json` to
+/// plaintext.
+#[tauri::command]
+fn clear_master_password(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: SecretString,
+) -> Result<(), String> {
+    assert_trusted_caller(&window)?;
+
+    let saved = load_accounts_from_disk(&app, Some(master_password.as_str()))?;
+    let path = accounts_path(&app)?;
+    let file = AccountsFile::Plain(saved);
+    std::fs::write(&path, serde_json::to_string(&file).unwrap()).map_err(|e| e.to_string())
+}
+
 // ── Command: get_student_info ───────────────────────────────────────
 
 #[tauri::command]
-async fn get_student_info(app: tauri::AppHandle) -> Result<Value, String> {
-    let (client, csrf, pid) = extract_session(&app)?;
-    api_get(&client, "/feign/student/student_data", &csrf, &pid).await
+async fn get_student_info(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+) -> Result<Value, ApiError> {
+    assert_trusted_caller(&window)?;
+    api_get(&app, "/feign/student/student_data").await
 }
 
 // ── Command: get_grades ─────────────────────────────────────────────
 
 #[tauri::command]
-async fn get_grades(app: tauri::AppHandle) -> Result<Value, String> {
-    let (client, csrf, pid) = extract_session(&app)?;
-    api_get(&client, "/feign/student/grades/all", &csrf, &pid).await
+async fn get_grades(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+) -> Result<Value, ApiError> {
+    assert_trusted_caller(&window)?;
+    api_get(&app, "/feign/student/grades/all").await
 }
 
 // ── Command: get_grade_stats ────────────────────────────────────────
@@ -385,15 +957,16 @@ struct GetGradeStatsArgs {
 #[tauri::command]
 async fn get_grade_stats(
     app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
     args: GetGradeStatsArgs,
-) -> Result<serde_json::Value, String> {
-    let (client, csrf, pid) = extract_session(&app)?;
+) -> Result<serde_json::Value, ApiError> {
+    assert_trusted_caller(&window)?;
     let path = format!(
         "/feign/student/grades/stats/course_syllabus/{}/exam_period/{}",
         args.course_syllabus_id,
         args.exam_period_id
     );
-    api_get(&client, &path, &csrf, &pid).await
+    api_get(&app, &path).await
 }
 
 // ── Command: get_keep_in_tray ───────────────────────────────────────
@@ -415,14 +988,104 @@ fn set_keep_in_tray(app: tauri::AppHandle, value: bool) -> Result<(), String> {
     save_settings(&app, &*guard)
 }
 
+// ── Command: get_poll_interval ──────────────────────────────────────
+
+#[tauri::command]
+fn get_poll_interval(app: tauri::AppHandle) -> Result<u64, String> {
+    let state = app.state::<AppState>();
+    let guard = state.settings.lock().map_err(|e| e.to_string())?;
+    Ok(guard.poll_interval_secs)
+}
+
+// ── Command: set_poll_interval ──────────────────────────────────────
+
+#[tauri::command]
+fn set_poll_interval(app: tauri::AppHandle, seconds: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut guard = state.settings.lock().map_err(|e| e.to_string())?;
+    guard.poll_interval_secs = seconds;
+    save_settings(&app, &*guard)
+}
+
 // ── Command: logout ─────────────────────────────────────────────────
 
 #[tauri::command]
-fn logout(app: tauri::AppHandle) -> Result<(), String> {
-    delete_session_from_disk(&app);
+fn logout(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    master_password: Option<SecretString>,
+) -> Result<(), String> {
+    assert_trusted_caller(&window)?;
+
     let state = app.state::<AppState>();
-    let mut guard = state.session.lock().map_err(|e| e.to_string())?;
-    *guard = None;
+    let label = {
+        let mut active = state.active_label.lock().map_err(|e| e.to_string())?;
+        active.take()
+    };
+    if let Some(label) = label {
+        let mut accounts = state.accounts.lock().map_err(|e| e.to_string())?;
+        accounts.remove(&label);
+        drop(accounts);
+        // Rewriting an encrypted accounts.json needs the master password;
+        // without it the on-disk entry and active_label would be left
+        // untouched, letting try_restore_session log the account right back
+        // in. Surface that instead of swallowing it, same as remove_account.
+        delete_account_from_disk(&app, &label, master_password.as_deref())?;
+    }
+    let _ = rebuild_tray_menu(&app);
+    Ok(())
+}
+
+// ── Tray menu ────────────────────────────────────────────────────────
+
+fn build_tray_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+
+    let state = app.state::<AppState>();
+    let accounts = state.accounts.lock().unwrap();
+    let active = state.active_label.lock().unwrap();
+
+    let mut labels: Vec<&String> = accounts.keys().collect();
+    labels.sort();
+
+    let account_items: Vec<MenuItem<tauri::Wry>> = labels
+        .iter()
+        .map(|label| {
+            let acc = &accounts[label.as_str()];
+            let checked = active.as_deref() == Some(label.as_str());
+            let text = format!(
+                "{}{} ({})",
+                if checked { "✓ " } else { "" },
+                label,
+                acc.username
+            );
+            MenuItem::with_id(app, format!("switch:{label}"), text, !checked, None::<&str>)
+        })
+        .collect::<tauri::Result<_>>()?;
+    drop(accounts);
+    drop(active);
+
+    let accounts_submenu = if account_items.is_empty() {
+        let placeholder =
+            MenuItem::with_id(app, "no-accounts", "No saved accounts", false, None::<&str>)?;
+        Submenu::with_items(app, "Accounts", true, &[&placeholder])?
+    } else {
+        let refs: Vec<&dyn IsMenuItem<tauri::Wry>> = account_items
+            .iter()
+            .map(|i| i as &dyn IsMenuItem<tauri::Wry>)
+            .collect();
+        Submenu::with_items(app, "Accounts", true, &refs)?
+    };
+
+    Menu::with_items(app, &[&show_i, &accounts_submenu, &quit_i])
+}
+
+fn rebuild_tray_menu(app: &tauri::AppHandle) -> tauri::Result<()> {
+    let menu = build_tray_menu(app)?;
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(menu))?;
+    }
     Ok(())
 }
 
@@ -432,19 +1095,30 @@ fn logout(app: tauri::AppHandle) -> Result<(), String> {
 pub fn run() {
     tauri::Builder::default()
         .manage(AppState {
-            session: Mutex::new(None),
+            accounts: Mutex::new(HashMap::new()),
+            active_label: Mutex::new(None),
             settings: Mutex::new(AppSettings::default()),
+            poll_unseen: Mutex::new(0),
         })
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
             try_restore_session,
             login,
+            list_accounts,
+            add_account,
+            switch_account,
+            remove_account,
+            set_master_password,
+            clear_master_password,
             get_student_info,
             get_grades,
             get_grade_stats,
             logout,
             get_keep_in_tray,
-            set_keep_in_tray
+            set_keep_in_tray,
+            get_poll_interval,
+            set_poll_interval
         ])
         .setup(|app| {
             // Load settings from disk
@@ -452,13 +1126,15 @@ pub fn run() {
             let state = app.state::<AppState>();
             let mut guard = state.settings.lock().unwrap();
             *guard = settings;
+            drop(guard);
 
-            // Build tray with Show and Quit menu items
-            let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-            let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
+            // Start the background grade-polling loop
+            grades::spawn_polling_task(app.handle().clone());
 
-            let mut tray_builder = TrayIconBuilder::new()
+            // Build tray with Show, Accounts and Quit menu items
+            let menu = build_tray_menu(&app.handle())?;
+
+            let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
                 .menu(&menu)
                 .show_menu_on_left_click(false)
                 .on_menu_event(move |app, event| {
@@ -468,11 +1144,22 @@ pub fn run() {
                                 let _ = window.show();
                                 let _ = window.set_focus();
                             }
+                            grades::reset_unseen(app);
                         }
                         "quit" => {
                             app.exit(0);
                         }
-                        _ => {}
+                        id => {
+                            if let Some(label) = id.strip_prefix("switch:") {
+                                let app = app.clone();
+                                let label = label.to_string();
+                                tauri::async_runtime::spawn(async move {
+                                    if let Some(window) = app.get_webview_window("main") {
+                                        let _ = switch_account(label, app.clone(), window, None).await;
+                                    }
+                                });
+                            }
+                        }
                     }
                 })
                 .on_tray_icon_event(|tray, event| {
@@ -487,6 +1174,7 @@ pub fn run() {
                             let _ = window.show();
                             let _ = window.set_focus();
                         }
+                        grades::reset_unseen(app);
                     }
                 });
 
@@ -497,8 +1185,8 @@ pub fn run() {
 
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
                 let app = window.app_handle();
                 let state = app.state::<AppState>();
                 let guard = state.settings.lock();
@@ -510,6 +1198,10 @@ pub fn run() {
                     }
                 }
             }
+            WindowEvent::Focused(true) => {
+                grades::reset_unseen(window.app_handle());
+            }
+            _ => {}
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");